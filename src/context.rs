@@ -0,0 +1,56 @@
+use crate::job::{JobState, JobTaskRegistry, JobToRunAsync, WorkerConfig, WorkerPool};
+use crate::store::{InMemoryMetadataStore, MetaDataStorage};
+use crate::{JobSchedulerError, JobStoredData};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock as StdRwLock};
+use tokio::sync::broadcast::{self, Sender};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Capacity of the broadcast channels shared between the scheduler, the
+/// job creator and running jobs. Generous enough that a slow subscriber
+/// lags rather than a fast producer blocking.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Shared state threaded through the scheduler: the channels `JobCreator`
+/// uses to add jobs and report their outcome, the durable metadata store,
+/// and the bookkeeping needed to track and bound running jobs.
+pub struct Context {
+    pub job_create_tx: Sender<(JobStoredData, Arc<RwLock<Box<JobToRunAsync>>>)>,
+    pub job_created_tx: Sender<Result<Uuid, (JobSchedulerError, Option<Uuid>)>>,
+    pub job_result_tx: Sender<(Uuid, Result<bool, JobSchedulerError>)>,
+    pub job_progress_tx: Sender<(Uuid, f32)>,
+    pub job_states: Arc<RwLock<HashMap<Uuid, JobState>>>,
+    pub job_tasks: JobTaskRegistry,
+    pub worker_pool: StdRwLock<WorkerPool>,
+    pub metadata_storage: Arc<RwLock<Box<dyn MetaDataStorage + Send + Sync>>>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::new_with_storage(Box::new(InMemoryMetadataStore::default()))
+    }
+
+    pub fn new_with_storage(storage: Box<dyn MetaDataStorage + Send + Sync>) -> Self {
+        let (job_create_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (job_created_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (job_result_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (job_progress_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            job_create_tx,
+            job_created_tx,
+            job_result_tx,
+            job_progress_tx,
+            job_states: Arc::new(RwLock::new(HashMap::new())),
+            job_tasks: JobTaskRegistry::new(),
+            worker_pool: StdRwLock::new(WorkerPool::new(WorkerConfig::default())),
+            metadata_storage: Arc::new(RwLock::new(storage)),
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
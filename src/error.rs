@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Errors that can occur while creating, storing or running a job.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JobSchedulerError {
+    /// The job could not be added to the schedule.
+    CantAdd,
+    /// The job could not be removed from the schedule.
+    CantRemove,
+    /// Reading or writing job metadata in the configured `MetaDataStorage`
+    /// backend failed.
+    StorageError(String),
+    /// A job exhausted its configured `MaxRetries` budget without a
+    /// successful run.
+    MaxRetriesExceeded,
+}
+
+impl fmt::Display for JobSchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobSchedulerError::CantAdd => write!(f, "Could not add job"),
+            JobSchedulerError::CantRemove => write!(f, "Could not remove job"),
+            JobSchedulerError::StorageError(e) => write!(f, "Storage error: {}", e),
+            JobSchedulerError::MaxRetriesExceeded => write!(f, "Job exceeded its max retries"),
+        }
+    }
+}
+
+impl std::error::Error for JobSchedulerError {}
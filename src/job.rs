@@ -1,39 +1,174 @@
+use crate::job::retry::MaxRetries;
 use crate::job_scheduler::JobsSchedulerLocked;
+use crate::{JobSchedulerError, JobStoredData};
 use chrono::{DateTime, Utc};
 use cron::Schedule;
+use std::future::Future;
+use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
-pub type JobToRun = dyn FnMut(Uuid, JobsSchedulerLocked) + Send + Sync;
+pub mod creator;
+pub mod handle;
+pub mod progress;
+pub mod retry;
+pub mod state;
+pub mod tasks;
+pub mod worker;
+
+pub use handle::JobHandle;
+pub use state::JobState;
+pub(crate) use state::JobStateSink;
+pub use tasks::JobTaskRegistry;
+pub use worker::{WorkerConfig, WorkerPool};
+
+/// A job's asynchronous run body: given its `Uuid` and a handle back to the
+/// scheduler, returns a future resolving to whether the run succeeded.
+pub type JobToRun = dyn FnMut(
+        Uuid,
+        JobsSchedulerLocked,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, JobSchedulerError>> + Send>>
+    + Send
+    + Sync;
+
+/// The closure `JobCreator::add` builds around a [`JobLocked`]: spawns one
+/// run of the job (with its retry and state bookkeeping) on the scheduler's
+/// task registry. The returned future resolves once the run is spawned, not
+/// when it finishes.
+pub type JobToRunAsync =
+    dyn FnMut(Uuid, JobsSchedulerLocked) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync;
+
 pub struct JobLocked(pub(crate) Arc<RwLock<Job>>);
 
+impl Clone for JobLocked {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 pub struct Job {
     pub schedule: Schedule,
-    pub run: Box<JobToRun>,
+    pub run_fn: Box<JobToRun>,
     pub last_tick: Option<DateTime<Utc>>,
     pub job_id: Uuid,
     pub count: u32,
+    pub task_registry: Option<JobTaskRegistry>,
+    pub(crate) state_sink: Option<JobStateSink>,
+    pub max_retries: MaxRetries,
+    pub retry_count: u32,
+    pub requeued_at: Option<DateTime<Utc>>,
+    pub category: Option<String>,
 }
 
 impl JobLocked {
     pub fn new<T>(schedule: &str, run: T) -> Result<Self, Box<dyn std::error::Error>>
     where
         T: 'static,
-        T: FnMut(Uuid, JobsSchedulerLocked) + Send + Sync,
+        T: FnMut(
+                Uuid,
+                JobsSchedulerLocked,
+            )
+                -> Pin<Box<dyn Future<Output = Result<bool, JobSchedulerError>> + Send>>
+            + Send
+            + Sync,
     {
         let schedule: Schedule = Schedule::from_str(schedule)?;
-        Ok(Self {
-            0: Arc::new(RwLock::new(Job {
-                schedule,
-                run: Box::new(run),
-                last_tick: None,
-                job_id: Uuid::new_v4(),
-                count: 0,
-            })),
+        Ok(Self(Arc::new(RwLock::new(Job {
+            schedule,
+            run_fn: Box::new(run),
+            last_tick: None,
+            job_id: Uuid::new_v4(),
+            count: 0,
+            task_registry: None,
+            state_sink: None,
+            max_retries: MaxRetries::default(),
+            retry_count: 0,
+            requeued_at: None,
+            category: None,
+        }))))
+    }
+
+    /// Opt this job into the retry subsystem: a failed run is requeued
+    /// according to `max_retries` instead of being logged and dropped.
+    /// Defaults to `MaxRetries::NoRetry`.
+    pub fn with_retries(self, max_retries: MaxRetries) -> Self {
+        if let Ok(mut s) = self.0.write() {
+            s.max_retries = max_retries;
+        }
+        self
+    }
+
+    /// Label this job with a category, used to weight its permit cost in a
+    /// `WorkerConfig` (see `WorkerConfig::with_category_weight`). Uncategorized
+    /// jobs weigh 1 permit.
+    pub fn with_category(self, category: impl Into<String>) -> Self {
+        if let Ok(mut s) = self.0.write() {
+            s.category = Some(category.into());
+        }
+        self
+    }
+
+    /// This job's id.
+    pub fn guid(&self) -> Uuid {
+        self.0.read().map(|s| s.job_id).unwrap_or_default()
+    }
+
+    /// Snapshot of this job's metadata, as `JobCreator::add` persists it.
+    pub fn job_data(&self) -> Result<JobStoredData, JobSchedulerError> {
+        let s = self.0.read().map_err(|_| JobSchedulerError::CantAdd)?;
+        Ok(JobStoredData {
+            id: Some(s.job_id.into()),
+            max_retries: s.max_retries,
+            retry_count: s.retry_count,
+            requeued_at: s.requeued_at,
+            category: s.category.clone(),
+            next_run: s.schedule.upcoming(Utc).next(),
         })
     }
 
+    /// Attach the registry used to track this job's spawned run tasks, so
+    /// that a later call to [`Self::abort`] has something to abort.
+    pub(crate) fn set_task_registry(&self, task_registry: JobTaskRegistry) {
+        if let Ok(mut s) = self.0.write() {
+            s.task_registry = Some(task_registry);
+        }
+    }
+
+    /// Attach where `JobState` transitions get recorded, so a later call to
+    /// [`Self::abort`] can mark this job `Stopped`.
+    pub(crate) fn set_state_sink(&self, state_sink: JobStateSink) {
+        if let Ok(mut s) = self.0.write() {
+            s.state_sink = Some(state_sink);
+        }
+    }
+
+    /// Abort the currently-running task for this job, if one is in flight,
+    /// recording its `JobState` as `Stopped` if it was. Returns whether a
+    /// running task was found and aborted.
+    pub async fn abort(&self) -> bool {
+        let (registry, state_sink, job_id) = {
+            let s = match self.0.read() {
+                Ok(s) => s,
+                Err(e) => {
+                    tracing::error!("Error reading job {:?}", e);
+                    return false;
+                }
+            };
+            (s.task_registry.clone(), s.state_sink.clone(), s.job_id)
+        };
+        let aborted = match registry {
+            Some(registry) => registry.abort(job_id).await,
+            None => false,
+        };
+        if aborted {
+            if let Some(state_sink) = state_sink {
+                state_sink.set(job_id, JobState::Stopped).await;
+            }
+        }
+        aborted
+    }
+
     pub fn tick(&mut self) -> bool {
         let now = Utc::now();
         {
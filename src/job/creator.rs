@@ -1,19 +1,166 @@
 use crate::context::Context;
-use crate::job::{JobLocked, JobToRunAsync};
+use crate::job::retry::{backoff, MaxRetries, ShouldStop};
+use crate::job::state::JobState;
+use crate::job::worker::WorkerPool;
+use crate::job::{JobHandle, JobLocked, JobStateSink, JobTaskRegistry, JobToRunAsync};
 use crate::store::MetaDataStorage;
 use crate::{JobSchedulerError, JobStoredData};
+use chrono::Utc;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::broadcast::{Receiver, Sender};
 use tokio::sync::RwLock;
 use tracing::error;
 use uuid::Uuid;
 
+/// Base delay used for the exponential backoff applied between retries.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+/// Upper bound on the backoff delay applied between retries.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
 #[derive(Default)]
 pub struct JobCreator {}
 
 impl JobCreator {
+    async fn set_job_state(
+        job_states: &Arc<RwLock<std::collections::HashMap<Uuid, JobState>>>,
+        storage: &Arc<RwLock<Box<dyn MetaDataStorage + Send + Sync>>>,
+        job_id: Uuid,
+        state: JobState,
+    ) {
+        JobStateSink {
+            states: job_states.clone(),
+            storage: storage.clone(),
+        }
+        .set(job_id, state)
+        .await;
+    }
+
+    /// Drive one run of `job` to completion, then act on the outcome: publish
+    /// the result, record the new `JobState`, and on failure either emit a
+    /// terminal error or requeue the job for another attempt after a backoff
+    /// delay. A job with `MaxRetries::NoRetry` is never requeued: a failed
+    /// run is logged and dropped exactly as it was before retries existed.
+    ///
+    /// A requeued retry is re-dispatched through `job_tasks`, the same
+    /// registry the initial run is spawned on, so it shows up as that job's
+    /// current task for `JobLocked::abort` and is waited on by
+    /// `JobsSchedulerLocked::shutdown` like any other in-flight run.
+    #[allow(clippy::too_many_arguments)]
+    fn run_once(
+        job_id: Uuid,
+        job_scheduler: crate::job_scheduler::JobsSchedulerLocked,
+        job: JobLocked,
+        max_retries: MaxRetries,
+        mut retry_data: JobStoredData,
+        job_states: Arc<RwLock<std::collections::HashMap<Uuid, JobState>>>,
+        job_states_storage: Arc<RwLock<Box<dyn MetaDataStorage + Send + Sync>>>,
+        result_tx: Sender<(Uuid, Result<bool, JobSchedulerError>)>,
+        retry_error_tx: Sender<Result<Uuid, (JobSchedulerError, Option<Uuid>)>>,
+        worker_pool: WorkerPool,
+        job_tasks: JobTaskRegistry,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            let category = retry_data.category.clone();
+            let _permit = worker_pool.acquire(category.as_deref()).await;
+            JobCreator::set_job_state(&job_states, &job_states_storage, job_id, JobState::Running)
+                .await;
+            let job_done = {
+                let w = job.0.write();
+                if let Err(e) = w {
+                    error!("Error getting job {:?}", e);
+                    return;
+                }
+                let mut w = w.unwrap();
+                (w.run_fn)(job_id, job_scheduler.clone())
+            };
+            let job_done = job_done.await;
+            // Release the permit as soon as the run itself is done: holding
+            // it through the backoff delay below would park it doing
+            // nothing and starve the pool under a burst of failing jobs.
+            drop(_permit);
+            let result_for_publish = match &job_done {
+                Ok(val) => Ok(*val),
+                Err(e) => Err(e.clone()),
+            };
+            if let Err(e) = result_tx.send((job_id, result_for_publish)) {
+                error!("Error publishing job result {:?}", e);
+            }
+            let failed = match &job_done {
+                Err(e) => {
+                    error!("Error running job {:?} {:?}", job_id, e);
+                    true
+                }
+                Ok(val) => {
+                    if !val {
+                        error!("Error running job {:?}", job_id);
+                    }
+                    !val
+                }
+            };
+            if !failed {
+                JobCreator::set_job_state(
+                    &job_states,
+                    &job_states_storage,
+                    job_id,
+                    JobState::Completed,
+                )
+                .await;
+                return;
+            }
+            JobCreator::set_job_state(&job_states, &job_states_storage, job_id, JobState::Failed)
+                .await;
+            if matches!(max_retries, MaxRetries::NoRetry) {
+                return;
+            }
+            match max_retries.should_stop(retry_data.retry_count) {
+                ShouldStop::LimitReached => {
+                    if let Err(e) = retry_error_tx.send(Err((
+                        JobSchedulerError::MaxRetriesExceeded,
+                        Some(job_id),
+                    ))) {
+                        error!("Could not send terminal retry failure {:?}", e);
+                    }
+                }
+                ShouldStop::Requeue => {
+                    retry_data.retry_count += 1;
+                    retry_data.requeued_at = Some(Utc::now());
+                    if let Err(e) = job_states_storage
+                        .write()
+                        .await
+                        .add_or_update(retry_data.clone())
+                        .await
+                    {
+                        error!("Error persisting retry state {:?}", e);
+                    }
+                    let delay =
+                        backoff(RETRY_BASE_DELAY, retry_data.retry_count, RETRY_MAX_DELAY);
+                    tokio::time::sleep(delay).await;
+                    job_tasks
+                        .spawn(
+                            job_id,
+                            JobCreator::run_once(
+                                job_id,
+                                job_scheduler,
+                                job,
+                                max_retries,
+                                retry_data,
+                                job_states,
+                                job_states_storage,
+                                result_tx,
+                                retry_error_tx,
+                                worker_pool,
+                                job_tasks.clone(),
+                            ),
+                        )
+                        .await;
+                }
+            }
+        })
+    }
+
     async fn listen_to_additions(
         storage: Arc<RwLock<Box<dyn MetaDataStorage + Send + Sync>>>,
         mut rx: Receiver<(JobStoredData, Arc<RwLock<Box<JobToRunAsync>>>)>,
@@ -66,9 +213,23 @@ impl JobCreator {
         })
     }
 
-    pub fn add(context: &Context, mut job: JobLocked) -> Result<Uuid, JobSchedulerError> {
+    pub fn add(
+        context: &Context,
+        job: JobLocked,
+    ) -> Result<(Uuid, JobHandle), JobSchedulerError> {
         let tx = context.job_create_tx.clone();
         let mut rx = context.job_created_tx.subscribe();
+        let job_created_tx = context.job_created_tx.clone();
+        let result_tx = context.job_result_tx.clone();
+        let result_rx = context.job_result_tx.subscribe();
+        let job_states = context.job_states.clone();
+        let job_states_storage = context.metadata_storage.clone();
+        let job_tasks = context.job_tasks.clone();
+        let worker_pool = context
+            .worker_pool
+            .read()
+            .map(|pool| pool.clone())
+            .unwrap_or_default();
 
         let (done_tx, done_rx) = std::sync::mpsc::channel();
 
@@ -84,35 +245,51 @@ impl JobCreator {
                 return;
             }
             let data = data.unwrap();
-            let job: Box<JobToRunAsync> = Box::new(move |job_id, job_scheduler| {
-                let job = job.clone();
+            job.set_task_registry(job_tasks.clone());
+            job.set_state_sink(JobStateSink {
+                states: job_states.clone(),
+                storage: job_states_storage.clone(),
+            });
+            let max_retries = data.max_retries;
+            let data_for_retry = data.clone();
+            let result_tx_for_run = result_tx.clone();
+            let retry_error_tx_for_run = job_created_tx.clone();
+            let job_states_for_run = job_states.clone();
+            let job_states_storage_for_run = job_states_storage.clone();
+            let worker_pool_for_run = worker_pool.clone();
+            let job_tasks_for_run = job_tasks.clone();
+            let job_to_run: Box<JobToRunAsync> = Box::new(move |job_id, job_scheduler| {
+                let job_tasks = job_tasks_for_run.clone();
+                let run = JobCreator::run_once(
+                    job_id,
+                    job_scheduler,
+                    job.clone(),
+                    max_retries,
+                    data_for_retry.clone(),
+                    job_states_for_run.clone(),
+                    job_states_storage_for_run.clone(),
+                    result_tx_for_run.clone(),
+                    retry_error_tx_for_run.clone(),
+                    worker_pool_for_run.clone(),
+                    job_tasks.clone(),
+                );
                 Box::pin(async move {
-                    let job_done = {
-                        let w = job.0.write();
-                        if let Err(e) = w {
-                            error!("Error getting job {:?}", e);
-                            return;
-                        }
-                        let mut w = w.unwrap();
-                        w.run(job_scheduler)
-                    };
-                    let job_done = job_done.await;
-                    match job_done {
-                        Err(e) => {
-                            error!("Error running job {:?} {:?}", job_id, e);
-                        }
-                        Ok(val) => {
-                            if !val {
-                                error!("Error running job {:?}", job_id);
-                            }
-                        }
-                    }
+                    job_tasks.spawn(job_id, run).await;
                 })
             });
             let done_tx_on_send = done_tx.clone();
+            let job_states_for_queue = job_states.clone();
+            let job_states_storage_for_queue = job_states_storage.clone();
             tokio::spawn(async move {
-                let job = Arc::new(RwLock::new(job));
-                if let Err(_e) = tx.send((data, job)) {
+                JobCreator::set_job_state(
+                    &job_states_for_queue,
+                    &job_states_storage_for_queue,
+                    uuid,
+                    JobState::Queued,
+                )
+                .await;
+                let job_to_run = Arc::new(RwLock::new(job_to_run));
+                if let Err(_e) = tx.send((data, job_to_run)) {
                     error!("Error sending new job");
                     if let Err(e) = done_tx_on_send.send(Err(JobSchedulerError::CantAdd)) {
                         error!("Error sending failure of adding job {:?}", e);
@@ -122,21 +299,17 @@ impl JobCreator {
 
             while let Ok(val) = rx.recv().await {
                 match val {
-                    Ok(ret_uuid) => {
-                        if ret_uuid == uuid {
-                            if let Err(e) = done_tx.send(Ok(uuid)) {
-                                error!("Could not send successful addition {:?}", e);
-                            }
-                            break;
+                    Ok(ret_uuid) if ret_uuid == uuid => {
+                        if let Err(e) = done_tx.send(Ok(uuid)) {
+                            error!("Could not send successful addition {:?}", e);
                         }
+                        break;
                     }
-                    Err((e, Some(ret_uuid))) => {
-                        if ret_uuid == uuid {
-                            if let Err(e) = done_tx.send(Err(e)) {
-                                error!("Could not send failure {:?}", e);
-                            }
-                            break;
+                    Err((e, Some(ret_uuid))) if ret_uuid == uuid => {
+                        if let Err(e) = done_tx.send(Err(e)) {
+                            error!("Could not send failure {:?}", e);
                         }
+                        break;
                     }
                     _ => {}
                 }
@@ -147,6 +320,6 @@ impl JobCreator {
             error!("Could not receive done from add {:?}", e);
             JobSchedulerError::CantAdd
         })??;
-        Ok(uuid)
+        Ok((uuid, JobHandle::new(uuid, result_rx)))
     }
 }
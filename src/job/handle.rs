@@ -0,0 +1,35 @@
+use crate::JobSchedulerError;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+/// Returned by [`crate::job::creator::JobCreator::add`] alongside the job's
+/// `Uuid`, letting a caller await the outcome of the job's next run the same
+/// way a synchronous dispatcher awaits a `run_request.sync` reply.
+pub struct JobHandle {
+    job_id: Uuid,
+    rx: Receiver<(Uuid, Result<bool, JobSchedulerError>)>,
+}
+
+impl JobHandle {
+    pub(crate) fn new(
+        job_id: Uuid,
+        rx: Receiver<(Uuid, Result<bool, JobSchedulerError>)>,
+    ) -> Self {
+        Self { job_id, rx }
+    }
+
+    /// Wait for the next completed run of this job and return its outcome.
+    ///
+    /// Results for other jobs published on the shared channel are ignored.
+    pub async fn try_result(&mut self) -> Result<bool, JobSchedulerError> {
+        loop {
+            match self.rx.recv().await {
+                Ok((uuid, result)) if uuid == self.job_id => return result,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Err(JobSchedulerError::CantAdd),
+            }
+        }
+    }
+}
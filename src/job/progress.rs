@@ -0,0 +1,20 @@
+use crate::job_scheduler::JobsSchedulerLocked;
+use tokio::sync::broadcast::Receiver;
+use uuid::Uuid;
+
+impl JobsSchedulerLocked {
+    /// Subscribe to incremental progress reports (0.0-1.0) published by
+    /// currently running jobs, keyed by job `Uuid`.
+    pub fn subscribe_progress(&self) -> Receiver<(Uuid, f32)> {
+        self.context.job_progress_tx.subscribe()
+    }
+
+    /// Publish an incremental progress report for `job_id`. Intended to be
+    /// called from inside a long-running job via the `JobsSchedulerLocked`
+    /// handed to it.
+    pub fn report_progress(&self, job_id: Uuid, progress: f32) {
+        if let Err(e) = self.context.job_progress_tx.send((job_id, progress)) {
+            tracing::error!("Error reporting job progress {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+/// Upper bound on how many times a failed job run may be retried.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "postgres", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaxRetries {
+    /// The job has not opted into the retry subsystem: a failed run is
+    /// logged and dropped exactly as it was before retries existed.
+    #[default]
+    NoRetry,
+    Infinite,
+    Count(u32),
+}
+
+/// Decision made once a job run has failed and its `retry_count` is known.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShouldStop {
+    LimitReached,
+    Requeue,
+}
+
+impl MaxRetries {
+    /// Whether a job that has already been retried `retry_count` times should
+    /// be requeued again, or whether it has exhausted its retry budget.
+    ///
+    /// `MaxRetries::NoRetry` always reports `LimitReached`: callers that care
+    /// about the difference between "never configured" and "configured with
+    /// zero retries" should check for `MaxRetries::NoRetry` directly, since a
+    /// no-retry job shouldn't emit the same terminal error a real retry
+    /// budget running out would.
+    pub fn should_stop(&self, retry_count: u32) -> ShouldStop {
+        match self {
+            MaxRetries::NoRetry => ShouldStop::LimitReached,
+            MaxRetries::Infinite => ShouldStop::Requeue,
+            MaxRetries::Count(limit) => {
+                if retry_count >= *limit {
+                    ShouldStop::LimitReached
+                } else {
+                    ShouldStop::Requeue
+                }
+            }
+        }
+    }
+}
+
+/// Exponential backoff: `base * 2^attempt`, capped at `max`.
+pub fn backoff(base: Duration, attempt: u32, max: Duration) -> Duration {
+    base.checked_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+        .unwrap_or(max)
+        .min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_each_attempt_until_capped() {
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(60);
+        assert_eq!(backoff(base, 0, max), Duration::from_secs(1));
+        assert_eq!(backoff(base, 1, max), Duration::from_secs(2));
+        assert_eq!(backoff(base, 2, max), Duration::from_secs(4));
+        assert_eq!(backoff(base, 6, max), max);
+    }
+
+    #[test]
+    fn backoff_does_not_overflow_on_large_attempt() {
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(60);
+        assert_eq!(backoff(base, u32::MAX, max), max);
+    }
+
+    #[test]
+    fn should_stop_no_retry_is_always_limit_reached() {
+        assert_eq!(MaxRetries::NoRetry.should_stop(0), ShouldStop::LimitReached);
+        assert_eq!(MaxRetries::NoRetry.should_stop(5), ShouldStop::LimitReached);
+    }
+
+    #[test]
+    fn should_stop_infinite_always_requeues() {
+        assert_eq!(MaxRetries::Infinite.should_stop(0), ShouldStop::Requeue);
+        assert_eq!(MaxRetries::Infinite.should_stop(1_000), ShouldStop::Requeue);
+    }
+
+    #[test]
+    fn should_stop_count_stops_once_limit_is_reached() {
+        let max_retries = MaxRetries::Count(3);
+        assert_eq!(max_retries.should_stop(0), ShouldStop::Requeue);
+        assert_eq!(max_retries.should_stop(2), ShouldStop::Requeue);
+        assert_eq!(max_retries.should_stop(3), ShouldStop::LimitReached);
+        assert_eq!(max_retries.should_stop(4), ShouldStop::LimitReached);
+    }
+
+    #[test]
+    fn should_stop_count_zero_stops_immediately() {
+        assert_eq!(
+            MaxRetries::Count(0).should_stop(0),
+            ShouldStop::LimitReached
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crate::store::MetaDataStorage;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::error;
+use uuid::Uuid;
+
+/// Lifecycle state of a single job, tracked per `Uuid` and handed to
+/// `MetaDataStorage::update_job_state`. Neither `InMemoryMetadataStore` nor
+/// `SqlMetadataStore` stores it, so with those backends it does not survive
+/// a scheduler restart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Stopped,
+}
+
+/// Where a job run's `JobState` transitions are recorded: the in-memory
+/// `Context::job_states` map, plus whatever `MetaDataStorage` backend is
+/// configured. Shared by `JobCreator`
+/// (for state changes during a run) and `JobLocked::abort` (to record
+/// `Stopped` when a run is cancelled).
+#[derive(Clone)]
+pub(crate) struct JobStateSink {
+    pub states: Arc<RwLock<HashMap<Uuid, JobState>>>,
+    pub storage: Arc<RwLock<Box<dyn MetaDataStorage + Send + Sync>>>,
+}
+
+impl JobStateSink {
+    pub(crate) async fn set(&self, job_id: Uuid, state: JobState) {
+        self.states.write().await.insert(job_id, state);
+        if let Err(e) = self.storage.write().await.update_job_state(job_id, state).await {
+            error!("Error persisting job state {:?}", e);
+        }
+    }
+}
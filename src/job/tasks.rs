@@ -0,0 +1,159 @@
+use crate::job_scheduler::JobsSchedulerLocked;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::{AbortHandle, Id, JoinSet};
+use tracing::error;
+use uuid::Uuid;
+
+/// Central registry of every in-flight job-run task, keyed both by the
+/// `tokio::task::Id` tokio hands back from the `JoinSet` (to correlate a
+/// `JoinError` back to the job that panicked or was aborted) and by the
+/// job's own `Uuid` (so a single job's current run can be aborted on
+/// demand).
+#[derive(Clone)]
+pub struct JobTaskRegistry {
+    inner: Arc<Mutex<RegistryInner>>,
+    accepting: Arc<StdRwLock<bool>>,
+}
+
+struct RegistryInner {
+    tasks: JoinSet<()>,
+    handles: HashMap<Uuid, AbortHandle>,
+    ids: HashMap<Id, Uuid>,
+}
+
+impl Default for JobTaskRegistry {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RegistryInner {
+                tasks: JoinSet::new(),
+                handles: HashMap::new(),
+                ids: HashMap::new(),
+            })),
+            accepting: Arc::new(StdRwLock::new(true)),
+        }
+    }
+}
+
+impl JobTaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `fut` as the current run of `job_id`, registering it so it can
+    /// later be aborted individually or awaited on shutdown. Returns `false`
+    /// without spawning if the registry has already started shutting down.
+    ///
+    /// Also reaps any runs that have already finished: nothing else drains
+    /// the `JoinSet` during normal operation, so without this a long-lived
+    /// scheduler would accumulate one dead entry per completed run forever.
+    pub async fn spawn<F>(&self, job_id: Uuid, fut: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if !*self.accepting.read().unwrap() {
+            return false;
+        }
+        let mut inner = self.inner.lock().await;
+        Self::reap_finished(&mut inner);
+        let abort_handle = inner.tasks.spawn(fut);
+        inner.ids.insert(abort_handle.id(), job_id);
+        inner.handles.insert(job_id, abort_handle);
+        true
+    }
+
+    /// Remove every already-finished task from `handles`/`ids` without
+    /// blocking on any still-running one.
+    fn reap_finished(inner: &mut RegistryInner) {
+        while let Some(result) = inner.tasks.try_join_next_with_id() {
+            match result {
+                Ok((id, ())) => {
+                    Self::forget(inner, id);
+                }
+                Err(e) => {
+                    let job_id = Self::forget(inner, e.id());
+                    error!("Job task {:?} ended with error {:?}", job_id, e);
+                }
+            }
+        }
+    }
+
+    /// Drop the bookkeeping for finished task `id`, returning the job it ran.
+    /// The job's abort handle is only removed while it still points at this
+    /// task, so a newer run of the same job (e.g. a retry spawned from inside
+    /// this one) stays abortable.
+    fn forget(inner: &mut RegistryInner, id: Id) -> Option<Uuid> {
+        let job_id = inner.ids.remove(&id)?;
+        if inner.handles.get(&job_id).map(|h| h.id()) == Some(id) {
+            inner.handles.remove(&job_id);
+        }
+        Some(job_id)
+    }
+
+    /// Abort the task currently running `job_id`, if any. Returns whether a
+    /// running task was found and aborted.
+    pub async fn abort(&self, job_id: Uuid) -> bool {
+        let inner = self.inner.lock().await;
+        match inner.handles.get(&job_id) {
+            Some(handle) if !handle.is_finished() => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop accepting new runs and wait for every outstanding task to finish,
+    /// up to `timeout` (waiting indefinitely if `None`). Panics or aborts are
+    /// logged with the job `Uuid` they were running, when known.
+    pub async fn shutdown(&self, timeout: Option<Duration>) {
+        *self.accepting.write().unwrap() = false;
+        let drain = async {
+            let mut inner = self.inner.lock().await;
+            while let Some(result) = inner.tasks.join_next_with_id().await {
+                match result {
+                    Ok((id, ())) => {
+                        Self::forget(&mut inner, id);
+                    }
+                    Err(e) => {
+                        let job_id = Self::forget(&mut inner, e.id());
+                        error!("Job task {:?} ended with error {:?}", job_id, e);
+                    }
+                }
+            }
+        };
+        match timeout {
+            Some(timeout) => {
+                if tokio::time::timeout(timeout, drain).await.is_err() {
+                    error!("Timed out waiting for outstanding job tasks to finish");
+                }
+            }
+            None => drain.await,
+        }
+    }
+}
+
+impl JobsSchedulerLocked {
+    /// Cap how many job runs may execute concurrently. Excess due jobs queue
+    /// for a permit rather than being spawned unbounded.
+    pub fn with_worker_config(self, config: crate::job::worker::WorkerConfig) -> Self {
+        if let Ok(mut pool) = self.context.worker_pool.write() {
+            *pool = crate::job::worker::WorkerPool::new(config);
+        }
+        self
+    }
+
+    /// Stop accepting new job runs and wait, without a time limit, for every
+    /// outstanding run to finish.
+    pub async fn shutdown(&self) {
+        self.shutdown_with_timeout(None).await
+    }
+
+    /// Like [`Self::shutdown`], but gives up waiting after `timeout`.
+    pub async fn shutdown_with_timeout(&self, timeout: Option<Duration>) {
+        self.context.job_tasks.shutdown(timeout).await
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Configures how many job runs may execute concurrently, decoupling tick
+/// dispatch rate from execution parallelism.
+#[derive(Clone, Debug)]
+pub struct WorkerConfig {
+    pub max_concurrent_runs: usize,
+    category_weights: HashMap<String, u32>,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_runs: 10,
+            category_weights: HashMap::new(),
+        }
+    }
+}
+
+impl WorkerConfig {
+    pub fn new(max_concurrent_runs: usize) -> Self {
+        Self {
+            max_concurrent_runs,
+            ..Default::default()
+        }
+    }
+
+    /// Assign a heavier (or lighter) permit cost to jobs of the given
+    /// category, so e.g. heavy jobs occupy more of the pool than light ones.
+    pub fn with_category_weight(mut self, category: impl Into<String>, weight: u32) -> Self {
+        self.category_weights.insert(category.into(), weight);
+        self
+    }
+
+    fn weight_for(&self, category: Option<&str>) -> u32 {
+        let weight = category
+            .and_then(|c| self.category_weights.get(c))
+            .copied()
+            .unwrap_or(1);
+        weight.clamp(1, self.max_concurrent_runs.max(1) as u32)
+    }
+}
+
+/// Bounds concurrent job execution with a shared semaphore, so excess due
+/// jobs queue for a permit rather than overwhelming the runtime.
+#[derive(Clone)]
+pub struct WorkerPool {
+    semaphore: Arc<Semaphore>,
+    config: WorkerConfig,
+}
+
+impl WorkerPool {
+    pub fn new(config: WorkerConfig) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(config.max_concurrent_runs)),
+            config,
+        }
+    }
+
+    /// Acquire however many permits `category` is weighted for, blocking
+    /// until they're available. Holding the returned permit releases it all
+    /// at once on drop.
+    pub async fn acquire(&self, category: Option<&str>) -> Option<OwnedSemaphorePermit> {
+        let weight = self.config.weight_for(category);
+        Arc::clone(&self.semaphore)
+            .acquire_many_owned(weight)
+            .await
+            .ok()
+    }
+}
+
+impl Default for WorkerPool {
+    fn default() -> Self {
+        Self::new(WorkerConfig::default())
+    }
+}
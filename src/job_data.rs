@@ -0,0 +1,98 @@
+use crate::job::retry::MaxRetries;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "postgres")]
+use serde::{Deserialize, Serialize};
+
+/// A job's `Uuid` split into two `u64` halves, the wire representation
+/// `JobStoredData` carries so it can be persisted without pulling `uuid`'s
+/// own (de)serialization support into every backend.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "postgres", derive(Serialize, Deserialize))]
+pub struct JobUuid {
+    pub id1: u64,
+    pub id2: u64,
+}
+
+impl From<&JobUuid> for Uuid {
+    fn from(value: &JobUuid) -> Self {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&value.id1.to_be_bytes());
+        bytes[8..].copy_from_slice(&value.id2.to_be_bytes());
+        Uuid::from_bytes(bytes)
+    }
+}
+
+impl From<Uuid> for JobUuid {
+    fn from(value: Uuid) -> Self {
+        let bytes = value.into_bytes();
+        JobUuid {
+            id1: u64::from_be_bytes(bytes[..8].try_into().unwrap()),
+            id2: u64::from_be_bytes(bytes[8..].try_into().unwrap()),
+        }
+    }
+}
+
+/// Persisted state for a single scheduled job.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "postgres", derive(Serialize, Deserialize))]
+pub struct JobStoredData {
+    pub id: Option<JobUuid>,
+    /// How many times a failed run of this job may be retried.
+    pub max_retries: MaxRetries,
+    /// How many retries of the current failure have already been attempted.
+    pub retry_count: u32,
+    /// When this job was last requeued for a retry. Persisted so a durable
+    /// `MetaDataStorage` backend has the information available, but nothing
+    /// currently reads it back on startup — a restart does not yet resume
+    /// pending retries, only the in-process backoff/requeue loop does.
+    pub requeued_at: Option<DateTime<Utc>>,
+    /// Optional label used to weight this job's permit cost in a
+    /// `WorkerConfig`.
+    pub category: Option<String>,
+    /// Next time this job is due to run, used by `MetaDataStorage`
+    /// backends that query for due jobs instead of holding every schedule
+    /// in memory.
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+impl JobStoredData {
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        self.next_run
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, crate::JobSchedulerError> {
+        serde_json::to_vec(self)
+            .map_err(|e| crate::JobSchedulerError::StorageError(e.to_string()))
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::JobSchedulerError> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| crate::JobSchedulerError::StorageError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_uuid_round_trips_through_uuid() {
+        let uuid = Uuid::new_v4();
+        let job_uuid: JobUuid = uuid.into();
+        let round_tripped: Uuid = (&job_uuid).into();
+        assert_eq!(uuid, round_tripped);
+    }
+
+    #[test]
+    fn job_uuid_round_trips_nil_and_max() {
+        for uuid in [Uuid::nil(), Uuid::max()] {
+            let job_uuid: JobUuid = uuid.into();
+            let round_tripped: Uuid = (&job_uuid).into();
+            assert_eq!(uuid, round_tripped);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::context::Context;
+use crate::job::creator::JobCreator;
+use crate::job::{JobHandle, JobLocked};
+use crate::store::MetaDataStorage;
+use crate::{JobSchedulerError, JobStoredData};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A cheaply-cloneable handle to a running scheduler. Every clone shares the
+/// same underlying [`Context`], so configuring or observing one handle (via
+/// [`Self::with_worker_config`], [`Self::subscribe_progress`], ...) affects
+/// every other clone too.
+#[derive(Clone)]
+pub struct JobsSchedulerLocked {
+    pub(crate) context: Arc<Context>,
+}
+
+impl JobsSchedulerLocked {
+    pub fn new() -> Self {
+        Self {
+            context: Arc::new(Context::new()),
+        }
+    }
+
+    /// Use a durable `MetaDataStorage` backend (e.g. `SqlMetadataStore`)
+    /// instead of the in-memory default, so the schedule survives a restart.
+    pub fn new_with_storage(storage: Box<dyn MetaDataStorage + Send + Sync>) -> Self {
+        Self {
+            context: Arc::new(Context::new_with_storage(storage)),
+        }
+    }
+
+    /// Start the background task that persists newly-added jobs.
+    pub async fn init(&self) -> Result<(), JobSchedulerError> {
+        JobCreator::default().init(&self.context).await
+    }
+
+    /// Add a job to the schedule, returning its `Uuid` and a [`JobHandle`]
+    /// that can be awaited for the outcome of its next run.
+    pub fn add(&self, job: JobLocked) -> Result<(Uuid, JobHandle), JobSchedulerError> {
+        JobCreator::add(&self.context, job)
+    }
+
+    /// Jobs whose `next_run` has already passed, as reported by the
+    /// configured `MetaDataStorage` backend. Backends like `SqlMetadataStore`
+    /// query for due jobs directly; `InMemoryMetadataStore` reports none.
+    /// The scheduler does not call this itself: nothing in it runs a tick
+    /// loop over due jobs.
+    pub async fn due_jobs(&self) -> Result<Vec<JobStoredData>, JobSchedulerError> {
+        self.context.metadata_storage.write().await.due_jobs().await
+    }
+}
+
+impl Default for JobsSchedulerLocked {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,10 @@
+mod context;
+mod error;
+mod job_data;
+pub mod job;
+pub mod job_scheduler;
+pub mod store;
+
+pub use error::JobSchedulerError;
+pub use job_data::{JobStoredData, JobUuid};
+pub use job_scheduler::JobsSchedulerLocked;
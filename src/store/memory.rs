@@ -0,0 +1,31 @@
+use crate::store::MetaDataStorage;
+use crate::{JobSchedulerError, JobStoredData};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// The default `MetaDataStorage` backend: everything lives in process
+/// memory and is lost on restart. Good enough for a single scheduler
+/// process with no durability requirements.
+#[derive(Default)]
+pub struct InMemoryMetadataStore {
+    jobs: HashMap<Uuid, JobStoredData>,
+}
+
+#[async_trait]
+impl MetaDataStorage for InMemoryMetadataStore {
+    async fn add_or_update(&mut self, data: JobStoredData) -> Result<(), JobSchedulerError> {
+        let id: Uuid = data.id.as_ref().map(|b| b.into()).ok_or(JobSchedulerError::CantAdd)?;
+        self.jobs.insert(id, data);
+        Ok(())
+    }
+
+    async fn get(&mut self, id: Uuid) -> Result<Option<JobStoredData>, JobSchedulerError> {
+        Ok(self.jobs.get(&id).cloned())
+    }
+
+    async fn delete(&mut self, id: Uuid) -> Result<(), JobSchedulerError> {
+        self.jobs.remove(&id);
+        Ok(())
+    }
+}
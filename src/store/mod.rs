@@ -0,0 +1,42 @@
+use crate::job::JobState;
+use crate::{JobSchedulerError, JobStoredData};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+pub mod memory;
+#[cfg(feature = "postgres")]
+pub mod sql;
+
+pub use memory::InMemoryMetadataStore;
+#[cfg(feature = "postgres")]
+pub use sql::SqlMetadataStore;
+
+/// Persists job metadata so a schedule can survive a restart (and, for
+/// durable backends, be shared across scheduler processes).
+#[async_trait]
+pub trait MetaDataStorage {
+    async fn add_or_update(&mut self, data: JobStoredData) -> Result<(), JobSchedulerError>;
+    async fn get(&mut self, id: Uuid) -> Result<Option<JobStoredData>, JobSchedulerError>;
+    async fn delete(&mut self, id: Uuid) -> Result<(), JobSchedulerError>;
+
+    /// Record a job's current `JobState`. Backends that don't track
+    /// lifecycle state separately from `JobStoredData` can leave this as a
+    /// no-op; the in-memory `job_states` map is the source of truth either
+    /// way.
+    async fn update_job_state(
+        &mut self,
+        _id: Uuid,
+        _state: JobState,
+    ) -> Result<(), JobSchedulerError> {
+        Ok(())
+    }
+
+    /// Jobs whose `next_run` has already passed, for backends that query for
+    /// due jobs instead of holding every schedule in memory. Backends that
+    /// don't support such a query (like [`InMemoryMetadataStore`]) can leave
+    /// this as an empty result. Only `JobsSchedulerLocked::due_jobs` calls
+    /// this; the scheduler does not poll it on its own.
+    async fn due_jobs(&mut self) -> Result<Vec<JobStoredData>, JobSchedulerError> {
+        Ok(Vec::new())
+    }
+}